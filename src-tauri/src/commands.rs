@@ -1,6 +1,7 @@
 // Tauri IPC commands
 // Rust functions callable from the frontend (Svelte) via invoke()
 
+use crate::tray::{TrayHandle, TrayState};
 use crate::AppState;
 use tauri::State;
 use tauri_plugin_dialog::DialogExt;
@@ -19,25 +20,68 @@ pub fn get_api_token(state: State<'_, AppState>) -> Result<Option<String>, Strin
     Ok(state.get_token())
 }
 
-/// Native file dialog -- single file selection.
-/// Returns the selected file path as a string, or None if cancelled.
+/// Update the tray icon/tooltip to reflect where the sidecar is in a transcription job.
 #[tauri::command]
-pub fn select_file(app: tauri::AppHandle) -> Result<Option<String>, String> {
-    let file = app
-        .dialog()
-        .file()
-        .add_filter(
-            "Audio/Video Files",
-            &[
+pub fn set_tray_state(state: TrayState, tray: State<'_, TrayHandle>) -> Result<(), String> {
+    tray.set_state(state);
+    Ok(())
+}
+
+/// Update the tray icon's progress ring (0-100). Redraws are throttled to whole-percent steps.
+#[tauri::command]
+pub fn set_tray_progress(percent: u8, tray: State<'_, TrayHandle>) -> Result<(), String> {
+    tray.set_progress(percent);
+    Ok(())
+}
+
+/// A named extension filter for the native file dialog, e.g. `{ name: "Audio/Video Files",
+/// extensions: ["mp3", "wav"] }`.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct DialogFilter {
+    pub name: String,
+    pub extensions: Vec<String>,
+}
+
+/// Default filters used when the frontend doesn't request anything more specific.
+fn default_dialog_filters() -> Vec<DialogFilter> {
+    vec![
+        DialogFilter {
+            name: "Audio/Video Files".into(),
+            extensions: [
                 "mp3", "wav", "m4a", "flac", "ogg", "aac", "wma", "opus", "amr",
                 "mp4", "avi", "mov", "mkv", "3gp", "webm",
-            ],
-        )
-        .add_filter("All Files", &["*"])
-        .blocking_pick_file();
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+        },
+        DialogFilter {
+            name: "All Files".into(),
+            extensions: vec!["*".into()],
+        },
+    ]
+}
 
-    match file {
-        Some(path) => Ok(Some(path.to_string())),
+/// Native file dialog -- single file selection.
+/// Returns the selected file path as a string, or None if cancelled.
+#[tauri::command]
+pub fn select_file(
+    app: tauri::AppHandle,
+    filters: Option<Vec<DialogFilter>>,
+    tray: State<'_, TrayHandle>,
+) -> Result<Option<String>, String> {
+    let mut dialog = app.dialog().file();
+    for filter in filters.unwrap_or_else(default_dialog_filters) {
+        let extensions: Vec<&str> = filter.extensions.iter().map(String::as_str).collect();
+        dialog = dialog.add_filter(&filter.name, &extensions);
+    }
+
+    match dialog.blocking_pick_file() {
+        Some(path) => {
+            let path = path.to_string();
+            tray.push_recent(&app, path.clone());
+            Ok(Some(path))
+        }
         None => Ok(None),
     }
 }
@@ -45,22 +89,23 @@ pub fn select_file(app: tauri::AppHandle) -> Result<Option<String>, String> {
 /// Native file dialog -- multiple file selection.
 /// Returns a list of selected file paths, or empty list if cancelled.
 #[tauri::command]
-pub fn select_files(app: tauri::AppHandle) -> Result<Vec<String>, String> {
-    let files = app
-        .dialog()
-        .file()
-        .add_filter(
-            "Audio/Video Files",
-            &[
-                "mp3", "wav", "m4a", "flac", "ogg", "aac", "wma", "opus", "amr",
-                "mp4", "avi", "mov", "mkv", "3gp", "webm",
-            ],
-        )
-        .add_filter("All Files", &["*"])
-        .blocking_pick_files();
+pub fn select_files(
+    app: tauri::AppHandle,
+    filters: Option<Vec<DialogFilter>>,
+    tray: State<'_, TrayHandle>,
+) -> Result<Vec<String>, String> {
+    let mut dialog = app.dialog().file();
+    for filter in filters.unwrap_or_else(default_dialog_filters) {
+        let extensions: Vec<&str> = filter.extensions.iter().map(String::as_str).collect();
+        dialog = dialog.add_filter(&filter.name, &extensions);
+    }
 
-    match files {
-        Some(paths) => Ok(paths.into_iter().map(|f| f.to_string()).collect()),
+    match dialog.blocking_pick_files() {
+        Some(paths) => {
+            let paths: Vec<String> = paths.into_iter().map(|f| f.to_string()).collect();
+            tray.push_recent_many(&app, paths.clone());
+            Ok(paths)
+        }
         None => Ok(vec![]),
     }
 }
@@ -75,3 +120,47 @@ pub fn select_folder(app: tauri::AppHandle) -> Result<Option<String>, String> {
         None => Ok(None),
     }
 }
+
+/// Transcript export format, each paired with its native file dialog filter.
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptFormat {
+    Srt,
+    Vtt,
+    Txt,
+    Json,
+}
+
+impl TranscriptFormat {
+    fn filter(self) -> (&'static str, &'static [&'static str]) {
+        match self {
+            TranscriptFormat::Srt => ("SubRip", &["srt"]),
+            TranscriptFormat::Vtt => ("WebVTT", &["vtt"]),
+            TranscriptFormat::Txt => ("Plain Text", &["txt"]),
+            TranscriptFormat::Json => ("JSON", &["json"]),
+        }
+    }
+}
+
+/// Native save dialog for exporting a transcript.
+/// Returns the chosen path so the frontend can write the serialized transcript there,
+/// or None if the dialog was cancelled.
+#[tauri::command]
+pub fn save_transcript(
+    app: tauri::AppHandle,
+    default_name: String,
+    format: TranscriptFormat,
+) -> Result<Option<String>, String> {
+    let (name, extensions) = format.filter();
+    let path = app
+        .dialog()
+        .file()
+        .set_file_name(&default_name)
+        .add_filter(name, extensions)
+        .blocking_save_file();
+
+    match path {
+        Some(path) => Ok(Some(path.to_string())),
+        None => Ok(None),
+    }
+}
@@ -1,32 +1,209 @@
 // System tray management
 
+use std::sync::Mutex;
 use tauri::{
     image::Image,
-    menu::{MenuBuilder, MenuItemBuilder},
-    tray::TrayIconBuilder,
-    Manager,
+    menu::{CheckMenuItemBuilder, Menu, MenuBuilder, MenuItemBuilder, SubmenuBuilder},
+    tray::{TrayIcon, TrayIconBuilder},
+    AppHandle, Manager, Wry,
 };
 
-/// Create the system tray with show/hide/quit menu items
-pub fn create_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+/// Maximum number of entries kept in the tray's "recent files" submenu.
+const MAX_RECENT: usize = 8;
+
+/// Lifecycle state the tray icon reflects while a transcription job runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayState {
+    Idle,
+    Ready,
+    Recording,
+    Processing,
+    Done,
+}
+
+impl TrayState {
+    fn tooltip(self) -> &'static str {
+        match self {
+            TrayState::Idle => "KotobaTranscriber",
+            TrayState::Ready => "KotobaTranscriber - 準備完了",
+            TrayState::Recording => "KotobaTranscriber - 録音中",
+            TrayState::Processing => "KotobaTranscriber - 処理中",
+            TrayState::Done => "KotobaTranscriber - 完了",
+        }
+    }
+}
+
+/// Retained tray handle so IPC commands can push live icon/tooltip updates.
+pub struct TrayHandle {
+    icon: TrayIcon,
+    state: Mutex<TrayState>,
+    progress: Mutex<u8>,
+    recent: Mutex<Vec<String>>,
+    pinned: Mutex<bool>,
+    always_on_top: Mutex<bool>,
+}
+
+impl TrayHandle {
+    /// Re-render the tray icon for `state` and apply it along with a matching tooltip.
+    /// Resets the progress ring outside of active (`Recording`/`Processing`) states so a
+    /// stale, already-full ring doesn't linger into the next job.
+    pub fn set_state(&self, state: TrayState) {
+        *self.state.lock().unwrap() = state;
+        if !matches!(state, TrayState::Recording | TrayState::Processing) {
+            *self.progress.lock().unwrap() = 0;
+        }
+        self.redraw();
+        let _ = self.icon.set_tooltip(Some(state.tooltip()));
+    }
+
+    /// Update the progress ring, throttled to whole-percent steps to avoid excess redraws.
+    pub fn set_progress(&self, percent: u8) {
+        let percent = percent.min(100);
+        let mut current = self.progress.lock().unwrap();
+        if *current == percent {
+            return;
+        }
+        *current = percent;
+        drop(current);
+        self.redraw();
+    }
+
+    fn redraw(&self) {
+        let state = *self.state.lock().unwrap();
+        let percent = *self.progress.lock().unwrap();
+        let icon_data = render_tray_icon(state, percent);
+        let _ = self.icon.set_icon(Some(Image::new_owned(icon_data, 32, 32)));
+    }
+
+    /// Push `path` to the front of the recent-files MRU list (bounded to `MAX_RECENT`,
+    /// deduplicated) and rebuild the tray menu to reflect it.
+    pub fn push_recent(&self, app: &AppHandle, path: String) {
+        self.push_recent_many(app, std::iter::once(path));
+    }
+
+    /// Push each path in `paths` to the front of the recent-files MRU list (bounded to
+    /// `MAX_RECENT`, deduplicated) and rebuild the tray menu once, regardless of how many
+    /// paths are given.
+    pub fn push_recent_many(&self, app: &AppHandle, paths: impl IntoIterator<Item = String>) {
+        let mut recent = self.recent.lock().unwrap();
+        for path in paths {
+            recent.retain(|p| p != &path);
+            recent.insert(0, path);
+        }
+        recent.truncate(MAX_RECENT);
+        drop(recent);
+        self.rebuild_menu(app);
+    }
+
+    /// Clear the recent-files MRU list and rebuild the tray menu.
+    pub fn clear_recent(&self, app: &AppHandle) {
+        self.recent.lock().unwrap().clear();
+        self.rebuild_menu(app);
+    }
+
+    /// Toggle whether the main window is pinned across all virtual desktops.
+    pub fn toggle_pinned(&self, app: &AppHandle) {
+        let mut pinned = self.pinned.lock().unwrap();
+        *pinned = !*pinned;
+        let value = *pinned;
+        drop(pinned);
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.set_visible_on_all_workspaces(value);
+        }
+        self.rebuild_menu(app);
+    }
+
+    /// Toggle whether the main window is kept above other windows.
+    pub fn toggle_always_on_top(&self, app: &AppHandle) {
+        let mut always_on_top = self.always_on_top.lock().unwrap();
+        *always_on_top = !*always_on_top;
+        let value = *always_on_top;
+        drop(always_on_top);
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.set_always_on_top(value);
+        }
+        self.rebuild_menu(app);
+    }
+
+    /// Reapply the persisted pin / always-on-top state to the main window, e.g. after it's
+    /// re-shown from the tray.
+    pub fn reapply_window_state(&self, app: &AppHandle) {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.set_visible_on_all_workspaces(*self.pinned.lock().unwrap());
+            let _ = window.set_always_on_top(*self.always_on_top.lock().unwrap());
+        }
+    }
+
+    fn rebuild_menu(&self, app: &AppHandle) {
+        let recent = self.recent.lock().unwrap().clone();
+        let pinned = *self.pinned.lock().unwrap();
+        let always_on_top = *self.always_on_top.lock().unwrap();
+        if let Ok(menu) = build_menu(app, &recent, pinned, always_on_top) {
+            let _ = self.icon.set_menu(Some(menu));
+        }
+    }
+}
+
+/// Build the tray's menu (show/hide/recent-files/pin/always-on-top/quit), re-created
+/// whenever any of this state changes since `Menu` can't be mutated in place.
+fn build_menu(
+    app: &AppHandle,
+    recent: &[String],
+    pinned: bool,
+    always_on_top: bool,
+) -> tauri::Result<Menu<Wry>> {
     let show = MenuItemBuilder::with_id("show", "表示").build(app)?;
     let hide = MenuItemBuilder::with_id("hide", "非表示").build(app)?;
     let quit = MenuItemBuilder::with_id("quit", "終了").build(app)?;
 
-    let menu = MenuBuilder::new(app)
+    let mut recent_menu = SubmenuBuilder::new(app, "最近のファイル");
+    if recent.is_empty() {
+        let none = MenuItemBuilder::with_id("recent:none", "（なし）")
+            .enabled(false)
+            .build(app)?;
+        recent_menu = recent_menu.item(&none);
+    } else {
+        for path in recent {
+            let item = MenuItemBuilder::with_id(format!("recent:{path}"), path).build(app)?;
+            recent_menu = recent_menu.item(&item);
+        }
+        recent_menu = recent_menu.separator();
+        let clear = MenuItemBuilder::with_id("recent:clear", "クリア").build(app)?;
+        recent_menu = recent_menu.item(&clear);
+    }
+    let recent_submenu = recent_menu.build()?;
+
+    let pin = CheckMenuItemBuilder::with_id("pin", "すべてのデスクトップに固定")
+        .checked(pinned)
+        .build(app)?;
+    let always_on_top_item = CheckMenuItemBuilder::with_id("always_on_top", "常に最前面に表示")
+        .checked(always_on_top)
+        .build(app)?;
+
+    MenuBuilder::new(app)
         .item(&show)
         .item(&hide)
+        .item(&recent_submenu)
+        .separator()
+        .item(&pin)
+        .item(&always_on_top_item)
         .separator()
         .item(&quit)
-        .build()?;
+        .build()
+}
 
-    // Generate a 32x32 orange "K" icon (RGBA)
-    let icon_data = create_tray_icon_rgba();
+/// Create the system tray with show/hide/recent-files/pin/always-on-top/quit menu items
+pub fn create_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+    let menu = build_menu(&app.handle(), &[], false, false)?;
+
+    // Generate the initial (Idle) tray icon
+    let icon_data = render_tray_icon(TrayState::Idle, 0);
     let icon = Image::new_owned(icon_data, 32, 32);
 
-    TrayIconBuilder::new()
+    let tray = TrayIconBuilder::new()
         .icon(icon)
-        .tooltip("KotobaTranscriber")
+        .tooltip(TrayState::Idle.tooltip())
         .menu(&menu)
         .on_menu_event(move |app, event| match event.id().as_ref() {
             "show" => {
@@ -35,6 +212,9 @@ pub fn create_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
                     let _ = window.unminimize();
                     let _ = window.set_focus();
                 }
+                if let Some(tray) = app.try_state::<TrayHandle>() {
+                    tray.reapply_window_state(app);
+                }
             }
             "hide" => {
                 if let Some(window) = app.get_webview_window("main") {
@@ -44,7 +224,29 @@ pub fn create_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
             "quit" => {
                 app.exit(0);
             }
-            _ => {}
+            "recent:none" => {}
+            "recent:clear" => {
+                if let Some(tray) = app.try_state::<TrayHandle>() {
+                    tray.clear_recent(app);
+                }
+            }
+            "pin" => {
+                if let Some(tray) = app.try_state::<TrayHandle>() {
+                    tray.toggle_pinned(app);
+                }
+            }
+            "always_on_top" => {
+                if let Some(tray) = app.try_state::<TrayHandle>() {
+                    tray.toggle_always_on_top(app);
+                }
+            }
+            id => {
+                if let Some(path) = id.strip_prefix("recent:") {
+                    if let Some(window) = app.get_webview_window("main") {
+                        let _ = window.emit("open-recent", path);
+                    }
+                }
+            }
         })
         .on_tray_icon_event(|tray, event| {
             if let tauri::tray::TrayIconEvent::Click {
@@ -60,23 +262,42 @@ pub fn create_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
                         let _ = window.show();
                         let _ = window.unminimize();
                         let _ = window.set_focus();
+                        if let Some(tray) = app.try_state::<TrayHandle>() {
+                            tray.reapply_window_state(app);
+                        }
                     }
                 }
             }
         })
         .build(app)?;
 
+    app.manage(TrayHandle {
+        icon: tray,
+        state: Mutex::new(TrayState::Idle),
+        progress: Mutex::new(0),
+        recent: Mutex::new(Vec::new()),
+        pinned: Mutex::new(false),
+        always_on_top: Mutex::new(false),
+    });
+
     Ok(())
 }
 
-/// Generate a 32x32 orange "K" icon as RGBA byte data
-fn create_tray_icon_rgba() -> Vec<u8> {
+/// Generate a 32x32 RGBA tray icon variant for the given lifecycle state and progress
+/// percent (0-100), drawn as an arc sweeping clockwise from 12 o'clock around the glyph.
+fn render_tray_icon(state: TrayState, percent: u8) -> Vec<u8> {
     const SIZE: u32 = 32;
     let total_pixels = (SIZE * SIZE) as usize;
     let mut data = vec![0u8; total_pixels * 4];
 
-    // Orange: #FF9800
-    let (r, g, b): (u8, u8, u8) = (0xFF, 0x98, 0x00);
+    // Base fill color per state: muted grey (Idle), orange (Ready), red (Recording), blue (Processing), green (Done)
+    let (r, g, b): (u8, u8, u8) = match state {
+        TrayState::Idle => (0x9E, 0x9E, 0x9E),
+        TrayState::Ready => (0xFF, 0x98, 0x00),
+        TrayState::Recording => (0xF4, 0x43, 0x36),
+        TrayState::Processing => (0x21, 0x96, 0xF3),
+        TrayState::Done => (0x4C, 0xAF, 0x50),
+    };
 
     for y in 0..SIZE {
         for x in 0..SIZE {
@@ -107,6 +328,42 @@ fn create_tray_icon_rgba() -> Vec<u8> {
                     data[idx + 2] = 255;
                 }
             }
+
+            // Recording gets a white REC dot so it reads at a glance even once downscaled
+            if state == TrayState::Recording && dist < 3.5 {
+                data[idx] = 255;
+                data[idx + 1] = 255;
+                data[idx + 2] = 255;
+                data[idx + 3] = 255;
+            }
+
+            // Progress ring: an annulus around the glyph, filled clockwise from 12 o'clock.
+            // Only drawn while a job is actually running, so idle/ready/done icons render
+            // exactly as they did before progress tracking existed.
+            if matches!(state, TrayState::Recording | TrayState::Processing)
+                && dist >= 13.0
+                && dist <= 15.5
+            {
+                // 0 at 12 o'clock, increasing clockwise, normalized to 0..1
+                let theta = (cx).atan2(-cy);
+                let normalized = if theta < 0.0 {
+                    (theta + std::f32::consts::TAU) / std::f32::consts::TAU
+                } else {
+                    theta / std::f32::consts::TAU
+                };
+
+                if normalized <= percent as f32 / 100.0 {
+                    data[idx] = 255;
+                    data[idx + 1] = 255;
+                    data[idx + 2] = 255;
+                    data[idx + 3] = 255;
+                } else {
+                    data[idx] = 0x40;
+                    data[idx + 1] = 0x40;
+                    data[idx + 2] = 0x40;
+                    data[idx + 3] = 255;
+                }
+            }
         }
     }
 